@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use yaxpeax_6502::{InstDecoder, Instruction, N6502};
+use yaxpeax_arch::{AddressDiff, Arch, Decoder, LengthedInstruction, Reader, U8Reader};
+
+fuzz_target!(|data: &[u8]| {
+    let decoder = InstDecoder::default();
+    let mut reader = U8Reader::new(data);
+    let mut inst = Instruction::default();
+
+    if decoder.decode_into(&mut inst, &mut reader).is_ok() {
+        // decode_into must consume exactly as many bytes as `Instruction::len()` reports, in
+        // both directions: never less (stale trailing bytes) and never more (overrun).
+        let consumed =
+            Reader::<<N6502 as Arch>::Address, <N6502 as Arch>::Word>::total_offset(&mut reader);
+        assert_eq!(
+            inst.len(),
+            AddressDiff::<<N6502 as Arch>::Address>::from_const(consumed)
+        );
+    }
+});